@@ -3,15 +3,18 @@
 mod dex;
 mod pp;
 mod save_data;
+mod serialize;
 
 use std::{format, sync::LazyLock};
 
+use dex::PiiSpecies;
 use dioxus::{logger::tracing, prelude::*};
 use pp::{PiiSex, SDPiiPersonalData};
-use save_data::{decrypt_savedata, extract_piibox, SAVEDATA_SIZE};
+use save_data::{SaveData, SaveSlot};
 use web_sys::{
-    js_sys::{self, Reflect},
-    wasm_bindgen::JsValue,
+    js_sys::{self, Array, Date, Reflect, Uint8Array},
+    wasm_bindgen::{JsCast, JsValue},
+    Blob, BlobPropertyBag, HtmlAnchorElement, Url,
 };
 
 // ew
@@ -31,23 +34,156 @@ fn main() {
     dioxus::launch(App);
 }
 
+/// Hands `bytes` to the browser as a file download named `filename`.
+fn download_bytes(bytes: &[u8], filename: &str) {
+    let array = Uint8Array::from(bytes);
+    let blob_parts = Array::new();
+    blob_parts.push(&array.buffer());
+
+    let options = BlobPropertyBag::new();
+    options.set_type("application/octet-stream");
+    let blob = Blob::new_with_u8_array_sequence_and_options(&blob_parts, &options).unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let anchor: HtmlAnchorElement = document.create_element("a").unwrap().dyn_into().unwrap();
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    Url::revoke_object_url(&url).unwrap();
+}
+
+/// Imports a single Pii from a user-selected `.json` snippet (see
+/// [SDPiiPersonalData::from_json]) and appends it to the box.
+#[cfg(feature = "serde")]
+async fn onimport(
+    evt: Event<FormData>,
+    mut pii_box_signal: Signal<Vec<SDPiiPersonalData>>,
+    mut error_signal: Signal<Option<String>>,
+) {
+    let Some(file_engine) = evt.files() else {
+        return;
+    };
+    let files = file_engine.files();
+    let Some(file_name) = files.first().cloned() else {
+        return;
+    };
+    let Some(bytes) = file_engine.read_file(&file_name).await else {
+        *error_signal.write() = Some("could not read the selected file".to_string());
+        return;
+    };
+
+    let result = String::from_utf8(bytes)
+        .map_err(|e| e.to_string())
+        .and_then(|json| SDPiiPersonalData::from_json(&json).map_err(|e| e.to_string()));
+
+    match result {
+        Ok(pii) => {
+            *error_signal.write() = None;
+            pii_box_signal.write().push(pii);
+        }
+        Err(e) => *error_signal.write() = Some(e),
+    }
+}
+
+/// Export/import controls for trading individual Piis as JSON. Both render
+/// nothing when the `serde` feature is disabled.
+#[cfg(feature = "serde")]
+fn json_export_button(pii_box_signal: Signal<Vec<SDPiiPersonalData>>, index: usize) -> Element {
+    rsx! {
+        button {
+            class: "p-1.5 bg-stone-700 hover:bg-stone-600 rounded-md",
+            onclick: move |_| {
+                if let Ok(json) = pii_box_signal.read()[index].to_json() {
+                    download_bytes(json.as_bytes(), "pii.json");
+                }
+            },
+            "Export"
+        }
+    }
+}
+#[cfg(not(feature = "serde"))]
+fn json_export_button(_pii_box_signal: Signal<Vec<SDPiiPersonalData>>, _index: usize) -> Element {
+    rsx! {}
+}
+
+#[cfg(feature = "serde")]
+fn json_import_label(
+    pii_box_signal: Signal<Vec<SDPiiPersonalData>>,
+    error_signal: Signal<Option<String>>,
+) -> Element {
+    rsx! {
+        label {
+            class: "flex items-center p-2 bg-indigo-700 hover:bg-indigo-600 rounded-lg cursor-pointer",
+            "Import Pii",
+            input {
+                r#type: "file",
+                accept: ".json",
+                class: "hidden",
+                onchange: move |e| onimport(e, pii_box_signal, error_signal)
+            }
+        }
+    }
+}
+#[cfg(not(feature = "serde"))]
+fn json_import_label(
+    _pii_box_signal: Signal<Vec<SDPiiPersonalData>>,
+    _error_signal: Signal<Option<String>>,
+) -> Element {
+    rsx! {}
+}
+
 #[component]
 fn App() -> Element {
+    let mut savedata_signal: Signal<Option<SaveData>> = use_signal(|| None);
     let mut pii_box_signal: Signal<Vec<SDPiiPersonalData>> = use_signal(Vec::new);
-    let mut onfile =
-        async move |evt: Event<FormData>, mut pii_box_signal: Signal<Vec<SDPiiPersonalData>>| {
-            let Some(file_engine) = evt.files() else {
-                return;
-            };
-            let files = file_engine.files();
-            let file_name = files.first().unwrap().clone();
-            let mut save_file = file_engine.read_file(&file_name).await.unwrap();
-
-            assert_eq!(save_file.len(), SAVEDATA_SIZE);
-            decrypt_savedata(&mut save_file);
-            let pii_box = extract_piibox(&save_file);
-            *pii_box_signal.write() = std::mem::take(&mut pii_box.into_vec());
+    let mut error_signal: Signal<Option<String>> = use_signal(|| None);
+    let mut slot_signal: Signal<SaveSlot> = use_signal(|| SaveSlot::One);
+
+    let mut onfile = async move |evt: Event<FormData>,
+                                  mut savedata_signal: Signal<Option<SaveData>>,
+                                  mut pii_box_signal: Signal<Vec<SDPiiPersonalData>>,
+                                  mut error_signal: Signal<Option<String>>,
+                                  slot: SaveSlot| {
+        let Some(file_engine) = evt.files() else {
+            return;
+        };
+        let files = file_engine.files();
+        let Some(file_name) = files.first().cloned() else {
+            return;
         };
+        let Some(save_file) = file_engine.read_file(&file_name).await else {
+            *error_signal.write() = Some("could not read the selected file".to_string());
+            return;
+        };
+
+        match SaveData::load(&save_file, slot) {
+            Ok(save_data) => {
+                *error_signal.write() = None;
+                *pii_box_signal.write() = save_data.pii_box().to_vec();
+                *savedata_signal.write() = Some(save_data);
+            }
+            Err(e) => *error_signal.write() = Some(e.to_string()),
+        }
+    };
+
+    let mut download = move |_| {
+        let Some(mut save_data) = savedata_signal() else {
+            return;
+        };
+        if let Err(e) = save_data.set_pii_box(pii_box_signal()) {
+            *error_signal.write() = Some(e.to_string());
+            return;
+        }
+        match save_data.save() {
+            Ok(bytes) => {
+                download_bytes(&bytes, "savedata.bin");
+                *savedata_signal.write() = Some(save_data);
+            }
+            Err(e) => *error_signal.write() = Some(e.to_string()),
+        }
+    };
 
     rsx! {
         document::Stylesheet { href: asset!("/assets/tailwind.css") }
@@ -64,22 +200,54 @@ fn App() -> Element {
                         class: "italic text-4xl",
                         "A WIP save editor for Pokémon Rumble"
                     }
+                    if let Some(error) = error_signal() {
+                        div {
+                            class: "p-4 bg-red-900 text-red-100 rounded-lg",
+                            "Couldn't load this save file: {error}"
+                        }
+                    }
+                    label {
+                        "Save slot "
+                        select {
+                            onchange: move |e| {
+                                slot_signal.set(if e.value() == "2" { SaveSlot::Two } else { SaveSlot::One });
+                            },
+                            option { value: "1", "Slot 1" }
+                            option { value: "2", "Slot 2" }
+                        }
+                    }
                     label {
                         class: "flex flex-col items-center justify-center p-4 bg-stone-800 hover:bg-stone-700 rounded-lg",
                         "Select your savedata.bin file.",
                         input {
                             r#type: "file",
                             accept: ".bin",
-                            onchange: move |e| onfile(e, pii_box_signal)
+                            onchange: move |e| onfile(e, savedata_signal, pii_box_signal, error_signal, slot_signal())
                         }
                     }
                 }
             } else {
                 div {
-                    class: "flex gap-4 flex-wrap",
-                    // class: "w-fit flex flex-col gap-4",
-                    for pii in pii_box_signal.iter() {
-                        PiiListItem { pii: pii.clone() }
+                    class: "flex flex-col gap-4 w-full",
+                    div {
+                        class: "flex gap-4",
+                        button {
+                            class: "p-2 bg-emerald-700 hover:bg-emerald-600 rounded-lg",
+                            onclick: move |_| pii_box_signal.write().push(SDPiiPersonalData::default()),
+                            "Add Pii"
+                        }
+                        button {
+                            class: "p-2 bg-sky-700 hover:bg-sky-600 rounded-lg",
+                            onclick: download,
+                            "Download"
+                        }
+                        {json_import_label(pii_box_signal, error_signal)}
+                    }
+                    div {
+                        class: "flex gap-4 flex-wrap",
+                        for index in 0..pii_box_signal.len() {
+                            PiiListItem { key: "{index}", index, pii_box_signal }
+                        }
                     }
                 }
             }
@@ -88,7 +256,9 @@ fn App() -> Element {
 }
 
 #[component]
-fn PiiListItem(pii: SDPiiPersonalData) -> Element {
+fn PiiListItem(index: usize, pii_box_signal: Signal<Vec<SDPiiPersonalData>>) -> Element {
+    let pii = pii_box_signal.read()[index].clone();
+
     let sex_symbol = match pii.sex() {
         Ok(PiiSex::Male) => "♂",
         Ok(PiiSex::Female) => "♀",
@@ -103,25 +273,27 @@ fn PiiListItem(pii: SDPiiPersonalData) -> Element {
     } else {
         ""
     };
+
     rsx! {
         div {
-            // class: "flex flex-col p-4 bg-stone-800 hover:bg-stone-700 border-4 border-transparent active:border-white items-center gap-8 rounded-4xl",
-            class: "flex flex-col p-4 bg-stone-800 border-4 border-transparent items-center gap-8 rounded-4xl",
+            class: "flex flex-col p-4 bg-stone-800 border-4 border-transparent items-center gap-4 rounded-4xl",
             div {
                 class: "flex flex-col items-center",
-                img { src: pii.sprite_src(), alt: pii.name(), class: "w-[128]" }
+                img { src: pii.sprite_src(), alt: pii.name().to_string(), class: "w-[128]" }
                 p { class: "-mt-5 z-10 bg-emerald-600 rounded-md p-1.5", "Lvl. {pii.level}"}
             }
             div {
                 class: "flex flex-col",
                 p { class: "text-2xl {name_color}", "{pii_name}" }
                 {(1..=2).map( |move_no| {
-                    if let Some(move_) = pii.move_name(move_no) {
-                        rsx!(
+                    match pii.move_name(move_no) {
+                        Some(Ok(move_)) => rsx!(
                             p { class: "italic", "Knows {move_}" }
-                        )
-                    } else {
-                        rsx!()
+                        ),
+                        Some(Err(e)) => rsx!(
+                            p { class: "italic text-red-400", "{e}" }
+                        ),
+                        None => rsx!(),
                     }
                 })}
                 if pii.trainer_id == 1 {
@@ -129,6 +301,179 @@ fn PiiListItem(pii: SDPiiPersonalData) -> Element {
                 }
                 p { {pii.unix_time()} }
             }
+            div {
+                class: "flex flex-col gap-2 w-full text-black",
+                label {
+                    "Level "
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "65535",
+                        value: "{pii.level}",
+                        oninput: move |e| {
+                            if let Ok(level) = e.value().parse() {
+                                pii_box_signal.write()[index].level = level;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Species (dex #) "
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        value: "{pii.mons_no}",
+                        oninput: move |e| {
+                            if let Ok(mons_no) = e.value().parse::<u16>() {
+                                if let Ok(species) = PiiSpecies::try_from(mons_no) {
+                                    pii_box_signal.write()[index].set_species(species);
+                                }
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Move 1 id "
+                    input {
+                        r#type: "number",
+                        value: "{pii.move1_id}",
+                        oninput: move |e| {
+                            if let Ok(move1_id) = e.value().parse() {
+                                pii_box_signal.write()[index].move1_id = move1_id;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Move 2 id "
+                    input {
+                        r#type: "number",
+                        value: "{pii.move2_id}",
+                        oninput: move |e| {
+                            if let Ok(move2_id) = e.value().parse() {
+                                pii_box_signal.write()[index].move2_id = move2_id;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Trait "
+                    input {
+                        r#type: "number",
+                        value: "{pii.trait_}",
+                        oninput: move |e| {
+                            if let Ok(trait_) = e.value().parse() {
+                                pii_box_signal.write()[index].trait_ = trait_;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Sex "
+                    select {
+                        value: "{pii.sex}",
+                        onchange: move |e| {
+                            if let Ok(sex) = e.value().parse() {
+                                pii_box_signal.write()[index].sex = sex;
+                            }
+                        }
+                        option { value: "0", "Male" }
+                        option { value: "1", "Female" }
+                        option { value: "2", "Unknown" }
+                    }
+                }
+                label {
+                    "Bonus max HP "
+                    input {
+                        r#type: "number",
+                        value: "{pii.bonus_max_hp}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                pii_box_signal.write()[index].bonus_max_hp = v;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Bonus attack "
+                    input {
+                        r#type: "number",
+                        value: "{pii.bonus_attack_power}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                pii_box_signal.write()[index].bonus_attack_power = v;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Bonus defence "
+                    input {
+                        r#type: "number",
+                        value: "{pii.bonus_defence_power}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                pii_box_signal.write()[index].bonus_defence_power = v;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Bonus speed "
+                    input {
+                        r#type: "number",
+                        value: "{pii.bonus_speed}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value().parse() {
+                                pii_box_signal.write()[index].bonus_speed = v;
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Shiny "
+                    input {
+                        r#type: "checkbox",
+                        checked: "{pii.is_shiny()}",
+                        oninput: move |e| {
+                            let shiny = e.value() == "true";
+                            pii_box_signal.write()[index].set_shiny(shiny);
+                        }
+                    }
+                }
+                label {
+                    "Created "
+                    input {
+                        r#type: "datetime-local",
+                        onchange: move |e| {
+                            let value = e.value();
+                            if !value.is_empty() {
+                                let ms = Date::new(&JsValue::from_str(&value)).get_time();
+                                pii_box_signal.write()[index].set_unix_time(ms);
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex gap-2",
+                button {
+                    class: "p-1.5 bg-stone-700 hover:bg-stone-600 rounded-md",
+                    onclick: move |_| {
+                        let clone = pii_box_signal.read()[index].clone();
+                        pii_box_signal.write().insert(index + 1, clone);
+                    },
+                    "Duplicate"
+                }
+                button {
+                    class: "p-1.5 bg-red-900 hover:bg-red-800 rounded-md",
+                    onclick: move |_| {
+                        pii_box_signal.write().remove(index);
+                    },
+                    "Remove"
+                }
+                {json_export_button(pii_box_signal, index)}
+            }
         }
     }
 }
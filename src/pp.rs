@@ -1,6 +1,6 @@
 use std::{
-    error::Error,
-    io::{Cursor, Write},
+    borrow::Cow,
+    io::{Read, Seek, Write},
     println,
 };
 
@@ -10,6 +10,8 @@ use crate::{
         GASTRODON_FORMS, GIRATINA_FORMS, MOVES, POKEMON_NAMES, ROTOM_FORMS, SHAYMIN_FORMS,
         SHELLOS_FORMS, TRAITS, UNOWN_FORMS, WORMADAM_FORMS,
     },
+    save_data::SaveError,
+    serialize::{FromReader, ToWriter},
     LOCALE,
 };
 use bitfield::bitfield;
@@ -34,6 +36,7 @@ bitfield! {
 }
 
 #[repr(u16)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(TryFromPrimitive, IntoPrimitive)]
 pub enum PiiSex {
     Male = 0,
@@ -54,13 +57,17 @@ impl std::fmt::Display for PiiSex {
 /// `SD_PiiPersonalData`. SD is SaveData, Pii is Wii Pokémon,
 /// and Personal Data refers to data specific to one pokémon entity.
 /// The name comes from RTTI in Pokémon Rumble's executable.
-#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct SDPiiPersonalData {
     /// National dex number.
+    #[cfg_attr(feature = "serde", serde(with = "json::species"))]
     pub mons_no: u16,
     pub form_no: u16,
     pub sex: u16,
+    #[cfg_attr(feature = "serde", serde(with = "json::move_id"))]
     pub move1_id: u16,
+    #[cfg_attr(feature = "serde", serde(with = "json::move_id"))]
     pub move2_id: u16,
     pub level: u16,
     pub bonus_max_hp: u32,
@@ -68,6 +75,7 @@ pub struct SDPiiPersonalData {
     pub bonus_defence_power: u32,
     pub bonus_speed: u32,
     /// Called `prefix` in the game's code.
+    #[cfg_attr(feature = "serde", serde(with = "json::trait_id"))]
     pub trait_: u16,
     /// Bitflags. Currently undocumented.
     pub flags: u16,
@@ -79,8 +87,109 @@ pub struct SDPiiPersonalData {
     pub trainer_id: u32,
 }
 
+/// JSON (de)serialization helpers, gated behind the `serde` feature. Species,
+/// move, and trait ids are exported by their human-readable name and accept
+/// either a name or a raw id on import, so a hand-edited JSON snippet that
+/// references an id that didn't exist in [crate::dex] still round-trips.
+#[cfg(feature = "serde")]
+mod json {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::dex::{PiiSpecies, MOVES, POKEMON_NAMES, TRAITS};
+
+    /// Either a human-readable name or, when none is recognized, the raw id.
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum NamedId {
+        Name(String),
+        Id(u16),
+    }
+
+    pub mod species {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(mons_no: &u16, s: S) -> Result<S::Ok, S::Error> {
+            let named = PiiSpecies::try_from(*mons_no)
+                .ok()
+                .and_then(|_| POKEMON_NAMES.get(*mons_no as usize - 1))
+                .map(|name| NamedId::Name(name.to_string()))
+                .unwrap_or(NamedId::Id(*mons_no));
+            named.serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u16, D::Error> {
+            match NamedId::deserialize(d)? {
+                NamedId::Id(id) => Ok(id),
+                NamedId::Name(name) => POKEMON_NAMES
+                    .iter()
+                    .position(|n| *n == name)
+                    .map(|i| i as u16 + 1)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unknown species {name:?}"))),
+            }
+        }
+    }
+
+    pub mod move_id {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(move_id: &u16, s: S) -> Result<S::Ok, S::Error> {
+            let named = MOVES
+                .get(*move_id as usize)
+                .map(|name| NamedId::Name(name.to_string()))
+                .unwrap_or(NamedId::Id(*move_id));
+            named.serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u16, D::Error> {
+            match NamedId::deserialize(d)? {
+                NamedId::Id(id) => Ok(id),
+                NamedId::Name(name) => MOVES
+                    .iter()
+                    .position(|n| *n == name)
+                    .map(|i| i as u16)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unknown move {name:?}"))),
+            }
+        }
+    }
+
+    pub mod trait_id {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(trait_id: &u16, s: S) -> Result<S::Ok, S::Error> {
+            let named = if *trait_id == 0 {
+                NamedId::Id(0)
+            } else {
+                TRAITS
+                    .get(*trait_id as usize - 1)
+                    .map(|t| NamedId::Name(t.name.to_string()))
+                    .unwrap_or(NamedId::Id(*trait_id))
+            };
+            named.serialize(s)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u16, D::Error> {
+            match NamedId::deserialize(d)? {
+                NamedId::Id(id) => Ok(id),
+                NamedId::Name(name) => TRAITS
+                    .iter()
+                    .position(|t| t.name == name)
+                    .map(|i| i as u16 + 1)
+                    .ok_or_else(|| serde::de::Error::custom(format!("unknown trait {name:?}"))),
+            }
+        }
+    }
+}
+
 impl SDPiiPersonalData {
-    pub fn move_name(&self, move_no: u8) -> Option<&'static str> {
+    /// Speed (in game-clock ticks per real second) at which `time` advances.
+    const OS_TIME_SPEED: f64 = 243_000_000.0 / 4.0; // 60_750_000.0
+    /// Unix timestamp (in seconds) of the console's 2000-01-01 epoch.
+    const UNIX_EPOCH_OFFSET: f64 = 946_684_800.0;
+
+    /// Looks up the name of move 1 or 2. Returns `None` when `move_no` isn't
+    /// `1`/`2` or no move is equipped there, and `Some(Err(SaveError::UnknownMove))`
+    /// rather than an out-of-bounds index when the equipped id isn't recognized.
+    pub fn move_name(&self, move_no: u8) -> Option<Result<Cow<'static, str>, SaveError>> {
         let move_id: usize = match move_no {
             1 => self.move1_id.into(),
             2 => self.move2_id.into(),
@@ -88,14 +197,21 @@ impl SDPiiPersonalData {
         };
 
         if move_id == 0 {
-            None
-        } else {
-            Some(MOVES[move_id])
+            return None;
         }
+
+        Some(
+            MOVES
+                .get(move_id)
+                .map(|name| Cow::Borrowed(*name))
+                .ok_or(SaveError::UnknownMove(move_id as u16)),
+        )
     }
 
-    pub fn name(&self) -> &str {
-        self.name_and_poke_api_sprite_id().0
+    pub fn name(&self) -> Cow<'static, str> {
+        self.name_and_poke_api_sprite_id()
+            .map(|(name, _)| name)
+            .unwrap_or_else(|e| Cow::Owned(e.to_string()))
     }
 
     pub fn trait_(&self) -> Option<&'static Trait> {
@@ -105,36 +221,53 @@ impl SDPiiPersonalData {
         TRAITS.get(self.trait_ as usize - 1)
     }
 
-    /// Accounts for a Pii's alternate forms.
-    pub fn name_and_poke_api_sprite_id(&self) -> (&str, &str) {
-        // TODO: handle invalid species
-        let species = PiiSpecies::try_from(self.mons_no).unwrap();
-        match species {
-            PiiSpecies::UNOWN => UNOWN_FORMS[self.form_no as usize],
-            PiiSpecies::CASTFORM => CASTFORM_FORMS[self.form_no as usize],
-            PiiSpecies::DEOXYS => DEOXYS_FORMS[self.form_no as usize],
-            PiiSpecies::BURMY => BURMY_FORMS[self.form_no as usize],
-            PiiSpecies::WORMADAM => WORMADAM_FORMS[self.form_no as usize],
-            PiiSpecies::CHERRIM => CHERRIM_FORMS[self.form_no as usize],
-            PiiSpecies::SHELLOS => SHELLOS_FORMS[self.form_no as usize],
-            PiiSpecies::GASTRODON => GASTRODON_FORMS[self.form_no as usize],
-            PiiSpecies::ROTOM => ROTOM_FORMS[self.form_no as usize],
-            PiiSpecies::GIRATINA => GIRATINA_FORMS[self.form_no as usize],
-            PiiSpecies::SHAYMIN => SHAYMIN_FORMS[self.form_no as usize],
-            PiiSpecies::ARCEUS => ARCEUS_FORMS[self.form_no as usize],
+    /// Accounts for a Pii's alternate forms. Returns `Err(SaveError::UnknownSpecies)`
+    /// rather than panicking when `mons_no` isn't a recognized species.
+    pub fn name_and_poke_api_sprite_id(
+        &self,
+    ) -> Result<(Cow<'static, str>, Cow<'static, str>), SaveError> {
+        fn form(entry: (&'static str, &'static str)) -> (Cow<'static, str>, Cow<'static, str>) {
+            (Cow::Borrowed(entry.0), Cow::Borrowed(entry.1))
+        }
+
+        let species = PiiSpecies::try_from(self.mons_no)
+            .map_err(|_| SaveError::UnknownSpecies(self.mons_no))?;
+
+        // `form_no` is an unvalidated 5-bit field off the wire, so every alt-form
+        // table lookup has to be bounds-checked rather than indexed directly.
+        let alt_form = |table: &'static [(&'static str, &'static str)]| {
+            table
+                .get(self.form_no as usize)
+                .copied()
+                .map(form)
+                .ok_or(SaveError::UnknownSpecies(self.mons_no))
+        };
+
+        Ok(match species {
+            PiiSpecies::UNOWN => alt_form(UNOWN_FORMS)?,
+            PiiSpecies::CASTFORM => alt_form(CASTFORM_FORMS)?,
+            PiiSpecies::DEOXYS => alt_form(DEOXYS_FORMS)?,
+            PiiSpecies::BURMY => alt_form(BURMY_FORMS)?,
+            PiiSpecies::WORMADAM => alt_form(WORMADAM_FORMS)?,
+            PiiSpecies::CHERRIM => alt_form(CHERRIM_FORMS)?,
+            PiiSpecies::SHELLOS => alt_form(SHELLOS_FORMS)?,
+            PiiSpecies::GASTRODON => alt_form(GASTRODON_FORMS)?,
+            PiiSpecies::ROTOM => alt_form(ROTOM_FORMS)?,
+            PiiSpecies::GIRATINA => alt_form(GIRATINA_FORMS)?,
+            PiiSpecies::SHAYMIN => alt_form(SHAYMIN_FORMS)?,
+            PiiSpecies::ARCEUS => alt_form(ARCEUS_FORMS)?,
             _ => (
-                POKEMON_NAMES[self.mons_no as usize - 1],
-                &*self.mons_no.to_string().leak(),
+                POKEMON_NAMES
+                    .get(self.mons_no as usize - 1)
+                    .map(|name| Cow::Borrowed(*name))
+                    .unwrap_or_else(|| Cow::Owned(self.mons_no.to_string())),
+                Cow::Owned(self.mons_no.to_string()),
             ),
-        }
+        })
     }
 
     pub fn unix_time(&self) -> String {
-        const OS_TIME_SPEED: f64 = 243_000_000.0 / 4.0; // 60_750_000.0
-
-        const UNIX_EPOCH_OFFSET: f64 = 946_684_800.0;
-
-        let unix_secs = (self.time as f64) / OS_TIME_SPEED + UNIX_EPOCH_OFFSET;
+        let unix_secs = (self.time as f64) / Self::OS_TIME_SPEED + Self::UNIX_EPOCH_OFFSET;
         let unix_ms = unix_secs * 1000_f64;
         // let unix_ms= unix_secs ;
         let d = js_sys::Date::new(&unix_ms.into());
@@ -142,6 +275,13 @@ impl SDPiiPersonalData {
         d.to_locale_string(&LOCALE, &JsValue::undefined()).into()
     }
 
+    /// Inverse of [SDPiiPersonalData::unix_time]: sets the creation time from
+    /// a JS-style unix timestamp in milliseconds.
+    pub fn set_unix_time(&mut self, unix_ms: f64) {
+        let unix_secs = unix_ms / 1000_f64;
+        self.time = ((unix_secs - Self::UNIX_EPOCH_OFFSET) * Self::OS_TIME_SPEED) as u64;
+    }
+
     pub fn is_shiny(&self) -> bool {
         let trainer_id_high = self.trainer_id >> 16;
         let trainer_id_low = self.trainer_id & 0xFFFF;
@@ -154,6 +294,26 @@ impl SDPiiPersonalData {
         xor_result < 8
     }
 
+    /// Inverse of [SDPiiPersonalData::is_shiny]: solves for a `pii_id` that
+    /// satisfies (or breaks) the shininess inequality for this Pii's fixed
+    /// `trainer_id`, preserving `pii_id`'s low 16 bits.
+    pub fn set_shiny(&mut self, shiny: bool) {
+        let trainer_id_high = self.trainer_id >> 16;
+        let trainer_id_low = self.trainer_id & 0xFFFF;
+        let pii_id_low = self.pii_id & 0xFFFF;
+
+        // XORing these three in forces the overall xor_result to 0, which is shiny.
+        let shiny_forcing_high = trainer_id_high ^ trainer_id_low ^ pii_id_low;
+        let pii_id_high = if shiny {
+            shiny_forcing_high
+        } else {
+            // flip a high bit to push xor_result to 8, out of the shiny range.
+            shiny_forcing_high ^ 8
+        };
+
+        self.pii_id = (pii_id_high << 16) | pii_id_low;
+    }
+
     pub fn sex(&self) -> Result<PiiSex, TryFromPrimitiveError<PiiSex>> {
         let raw_sex = self.sex;
         PiiSex::try_from(raw_sex)
@@ -164,12 +324,29 @@ impl SDPiiPersonalData {
     }
 
     pub fn sprite_src(&self) -> String {
-        let poke_api_sprite_id = self.name_and_poke_api_sprite_id().1;
+        let poke_api_sprite_id = self
+            .name_and_poke_api_sprite_id()
+            .map(|(_, sprite_id)| sprite_id)
+            .unwrap_or_else(|_| Cow::Owned(self.mons_no.to_string()));
         format!(
             "https://raw.githubusercontent.com/PokeAPI/sprites/master/sprites/pokemon/other/home/{shiny_path}{poke_api_sprite_id}.png",
             shiny_path = if self.is_shiny() {"shiny/"} else {""}
         )
     }
+
+    /// Exports this Pii as a human-readable JSON snippet, for trading Piis as
+    /// text instead of whole save files.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Imports a Pii from a JSON snippet produced by [SDPiiPersonalData::to_json].
+    /// `is_shiny()`/`sprite_src()` are computed from the restored fields, not cached.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
 }
 
 /// A Pii's special trait. See <https://bulbapedia.bulbagarden.net/wiki/Special_Traits>
@@ -203,30 +380,47 @@ impl From<U32> for (u16, u16) {
     }
 }
 
-/// Extends [ReadBytesExt] with methods for reading [SDPiiPersonalData].
-pub trait ReadSDPiiPersonalData: ReadBytesExt {
-    fn read_sd_ppd(&mut self) -> Result<SDPiiPersonalData, Box<dyn Error>> {
-        let packed_mons_no_form_no = self.read_u16::<BigEndian>()?;
-        let move1_id = self.read_u16::<BigEndian>()?;
-        let move2_id = self.read_u16::<BigEndian>()?;
-        let level = self.read_u16::<BigEndian>()?;
-        let lo_bonus_max_hp = self.read_u16::<BigEndian>()?;
-        let lo_bonus_attack_power = self.read_u16::<BigEndian>()?;
-        let lo_bonus_defence_power = self.read_u16::<BigEndian>()?;
-        let lo_bonus_speed = self.read_u16::<BigEndian>()?;
-        let trait_ = self.read_u16::<BigEndian>()?;
-        let flags = self.read_u16::<BigEndian>()?;
-        let pii_id = self.read_u32::<BigEndian>()?;
-        let hi_bonus_max_hp = self.read_u16::<BigEndian>()?;
-        let hi_bonus_attack_power = self.read_u16::<BigEndian>()?;
-        let hi_bonus_defence_power = self.read_u16::<BigEndian>()?;
-        let hi_bonus_speed = self.read_u16::<BigEndian>()?;
-        let time = self.read_u64::<BigEndian>()?;
-        let trainer_id = self.read_u32::<BigEndian>()?;
-        // idk what this is
-        assert_eq!(self.read_u8()?, 0);
+impl FromReader for SDPiiPersonalDataPacked {
+    const STATIC_SIZE: usize = 2;
 
-        let packed = SDPiiPersonalDataPacked(packed_mons_no_form_no);
+    fn from_reader<R: Read + Seek>(r: &mut R) -> Result<Self, SaveError> {
+        Ok(SDPiiPersonalDataPacked(r.read_u16::<BigEndian>()?))
+    }
+}
+
+impl ToWriter for SDPiiPersonalDataPacked {
+    fn to_writer<W: Write>(&self, w: &mut W) -> Result<(), SaveError> {
+        w.write_u16::<BigEndian>(self.0)?;
+        Ok(())
+    }
+}
+
+impl FromReader for SDPiiPersonalData {
+    const STATIC_SIZE: usize = 0x2D;
+
+    fn from_reader<R: Read + Seek>(r: &mut R) -> Result<Self, SaveError> {
+        let packed = SDPiiPersonalDataPacked::from_reader(r)?;
+        let move1_id = r.read_u16::<BigEndian>()?;
+        let move2_id = r.read_u16::<BigEndian>()?;
+        let level = r.read_u16::<BigEndian>()?;
+        let lo_bonus_max_hp = r.read_u16::<BigEndian>()?;
+        let lo_bonus_attack_power = r.read_u16::<BigEndian>()?;
+        let lo_bonus_defence_power = r.read_u16::<BigEndian>()?;
+        let lo_bonus_speed = r.read_u16::<BigEndian>()?;
+        let trait_ = r.read_u16::<BigEndian>()?;
+        let flags = r.read_u16::<BigEndian>()?;
+        let pii_id = r.read_u32::<BigEndian>()?;
+        let hi_bonus_max_hp = r.read_u16::<BigEndian>()?;
+        let hi_bonus_attack_power = r.read_u16::<BigEndian>()?;
+        let hi_bonus_defence_power = r.read_u16::<BigEndian>()?;
+        let hi_bonus_speed = r.read_u16::<BigEndian>()?;
+        let time = r.read_u64::<BigEndian>()?;
+        let trainer_id = r.read_u32::<BigEndian>()?;
+        // idk what this is
+        let reserved = r.read_u8()?;
+        if reserved != 0 {
+            return Err(SaveError::NonZeroReserved(reserved));
+        }
 
         Ok(SDPiiPersonalData {
             mons_no: packed.mons_no(),
@@ -247,55 +441,50 @@ pub trait ReadSDPiiPersonalData: ReadBytesExt {
         })
     }
 }
-impl<R: std::io::Read + ?Sized> ReadSDPiiPersonalData for R {}
 
-/// Extends [WriteBytesExt] with methods for writing [SDPiiPersonalData].
-pub trait WriteSDPiiPersonalData: WriteBytesExt {
-    fn write_sd_ppd(&mut self, ppd: &SDPiiPersonalData) -> Result<(), Box<dyn Error>> {
-        let packed_mons_no_form_no =
-            SDPiiPersonalDataPacked::new(ppd.mons_no, ppd.form_no, ppd.sex);
+impl ToWriter for SDPiiPersonalData {
+    fn to_writer<W: Write>(&self, w: &mut W) -> Result<(), SaveError> {
+        let packed = SDPiiPersonalDataPacked::new(self.mons_no, self.form_no, self.sex);
 
-        let (hi_bonus_max_hp, lo_bonus_max_hp) = <(u16, u16)>::from(U32(ppd.bonus_max_hp));
+        let (hi_bonus_max_hp, lo_bonus_max_hp) = <(u16, u16)>::from(U32(self.bonus_max_hp));
         let (hi_bonus_attack_power, lo_bonus_attack_power) =
-            <(u16, u16)>::from(U32(ppd.bonus_attack_power));
+            <(u16, u16)>::from(U32(self.bonus_attack_power));
         let (hi_bonus_defence_power, lo_bonus_defence_power) =
-            <(u16, u16)>::from(U32(ppd.bonus_defence_power));
-        let (hi_bonus_speed, lo_bonus_speed) = <(u16, u16)>::from(U32(ppd.bonus_speed));
-
-        self.write_u16::<BigEndian>(packed_mons_no_form_no.0);
-        self.write_u16::<BigEndian>(ppd.move1_id)?;
-        self.write_u16::<BigEndian>(ppd.move2_id)?;
-        self.write_u16::<BigEndian>(ppd.level)?;
-        self.write_u16::<BigEndian>(lo_bonus_max_hp)?;
-        self.write_u16::<BigEndian>(lo_bonus_attack_power)?;
-        self.write_u16::<BigEndian>(lo_bonus_defence_power)?;
-        self.write_u16::<BigEndian>(lo_bonus_speed)?;
-        self.write_u16::<BigEndian>(ppd.trait_)?;
-        self.write_u16::<BigEndian>(ppd.flags)?;
-        self.write_u32::<BigEndian>(ppd.pii_id)?;
-        self.write_u16::<BigEndian>(hi_bonus_max_hp)?;
-        self.write_u16::<BigEndian>(hi_bonus_attack_power)?;
-        self.write_u16::<BigEndian>(hi_bonus_defence_power)?;
-        self.write_u16::<BigEndian>(hi_bonus_speed)?;
-        self.write_u64::<BigEndian>(ppd.time)?;
-        self.write_u32::<BigEndian>(ppd.trainer_id)?;
-        self.write_u8(0);
+            <(u16, u16)>::from(U32(self.bonus_defence_power));
+        let (hi_bonus_speed, lo_bonus_speed) = <(u16, u16)>::from(U32(self.bonus_speed));
+
+        packed.to_writer(w)?;
+        w.write_u16::<BigEndian>(self.move1_id)?;
+        w.write_u16::<BigEndian>(self.move2_id)?;
+        w.write_u16::<BigEndian>(self.level)?;
+        w.write_u16::<BigEndian>(lo_bonus_max_hp)?;
+        w.write_u16::<BigEndian>(lo_bonus_attack_power)?;
+        w.write_u16::<BigEndian>(lo_bonus_defence_power)?;
+        w.write_u16::<BigEndian>(lo_bonus_speed)?;
+        w.write_u16::<BigEndian>(self.trait_)?;
+        w.write_u16::<BigEndian>(self.flags)?;
+        w.write_u32::<BigEndian>(self.pii_id)?;
+        w.write_u16::<BigEndian>(hi_bonus_max_hp)?;
+        w.write_u16::<BigEndian>(hi_bonus_attack_power)?;
+        w.write_u16::<BigEndian>(hi_bonus_defence_power)?;
+        w.write_u16::<BigEndian>(hi_bonus_speed)?;
+        w.write_u64::<BigEndian>(self.time)?;
+        w.write_u32::<BigEndian>(self.trainer_id)?;
+        w.write_u8(0)?;
 
         Ok(())
     }
 }
-impl<R: std::io::Write + ?Sized> WriteSDPiiPersonalData for R {}
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{
         dex::PiiSpecies,
-        save_data::{decrypt_savedata, encrypt_savedata, extract_piibox, write_piibox},
+        save_data::{decrypt_savedata, encrypt_savedata, extract_piibox, write_piibox, SaveSlot},
     };
-    use byteorder::WriteBytesExt;
     use core::assert_eq;
-    use std::{fs, io::Write};
+    use std::io::Cursor;
 
     #[test]
     fn parse_savedata() {
@@ -304,10 +493,10 @@ mod test {
             *include_bytes!("../savedata.bin");
         let mut unaltered_savedata = savedata.clone();
 
-        decrypt_savedata(&mut savedata);
-        let mut pii_box = extract_piibox(&savedata).into_vec();
+        decrypt_savedata(&mut savedata).unwrap();
+        let mut pii_box = extract_piibox(&savedata, SaveSlot::One).unwrap().into_vec();
 
-        write_piibox(&mut savedata, &pii_box);
+        write_piibox(&mut savedata, &pii_box, SaveSlot::One).unwrap();
         encrypt_savedata(&mut savedata);
     }
 
@@ -319,4 +508,34 @@ mod test {
     fn u32_to_high_and_low() {
         assert_eq!(<(u16, u16)>::from(U32(0xaaaabbbb)), (0xaaaa, 0xbbbb));
     }
+
+    #[test]
+    fn sd_ppd_static_size_matches_cursor_advance() {
+        let ppd = SDPiiPersonalData {
+            mons_no: 25,
+            form_no: 0,
+            sex: 0,
+            move1_id: 1,
+            move2_id: 2,
+            level: 50,
+            bonus_max_hp: 1,
+            bonus_attack_power: 2,
+            bonus_defence_power: 3,
+            bonus_speed: 4,
+            trait_: 0,
+            flags: 0,
+            pii_id: 0x1234_5678,
+            time: 0x1122_3344_5566_7788,
+            trainer_id: 1,
+        };
+
+        let mut buf = Vec::new();
+        ppd.to_writer(&mut buf).unwrap();
+        assert_eq!(buf.len(), SDPiiPersonalData::STATIC_SIZE);
+
+        let mut cursor = Cursor::new(buf.as_slice());
+        let parsed = SDPiiPersonalData::from_reader(&mut cursor).unwrap();
+        assert_eq!(cursor.position() as usize, SDPiiPersonalData::STATIC_SIZE);
+        assert_eq!(parsed, ppd);
+    }
 }
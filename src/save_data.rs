@@ -1,4 +1,5 @@
-use crate::pp::{ReadSDPiiPersonalData, SDPiiPersonalData, WriteSDPiiPersonalData};
+use crate::pp::SDPiiPersonalData;
+use crate::serialize::{FromReader, ToWriter};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use sha1::{Digest, Sha1};
 use std::io::Cursor;
@@ -11,31 +12,172 @@ const CHUNK_SIZE: usize = 0x10;
 const CHUNK_COUNT: usize = SAVEDATA_SIZE / CHUNK_SIZE;
 
 /// offset (in bytes) into a `savedata.bin` file where the PiiBox data for save slot 1 is stored.
-const PIIBOX_SAVEDATA_OFFSET: usize = 0x1360;
-const SIZEOF_SDPPD: usize = 0x2D;
+const PIIBOX_SAVEDATA_OFFSET_SLOT_1: usize = 0x1360;
+/// offset (in bytes) into a `savedata.bin` file where the PiiBox data for save slot 2 is stored.
+/// The two save slots appear to evenly split the file; not independently confirmed.
+const PIIBOX_SAVEDATA_OFFSET_SLOT_2: usize = PIIBOX_SAVEDATA_OFFSET_SLOT_1 + SAVEDATA_SIZE / 2;
+/// Size (in bytes) of the region available to a single save slot, used only to
+/// bound how many piibox entries we'll trust a length header for.
+const SLOT_REGION_SIZE: usize = SAVEDATA_SIZE / 2;
+/// Bytes of a slot's region consumed by other slot data before its piibox
+/// header starts. The piibox itself only gets what's left after this.
+const PIIBOX_IN_SLOT_OFFSET: usize = PIIBOX_SAVEDATA_OFFSET_SLOT_1;
+/// Largest piibox entry count that fits within a slot's region, after the
+/// header. A length header above this is rejected instead of driving a
+/// read past the slot (and into the next one).
+const MAX_PIIBOX_LEN: usize = (SLOT_REGION_SIZE - PIIBOX_IN_SLOT_OFFSET - PiiBox::STATIC_SIZE)
+    / SDPiiPersonalData::STATIC_SIZE;
 
-pub fn extract_piibox(savedata: &[u8]) -> Box<[SDPiiPersonalData]> {
+/// Which of a save file's two save slots to operate on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveSlot {
+    One,
+    Two,
+}
+
+impl SaveSlot {
+    fn piibox_offset(self) -> usize {
+        match self {
+            SaveSlot::One => PIIBOX_SAVEDATA_OFFSET_SLOT_1,
+            SaveSlot::Two => PIIBOX_SAVEDATA_OFFSET_SLOT_2,
+        }
+    }
+}
+
+/// Errors that can occur while reading or writing save data.
+#[derive(Debug)]
+pub enum SaveError {
+    /// The reader ran out of bytes before a struct could be fully read.
+    UnexpectedEof,
+    /// The header SHA1 stored in the save data doesn't match its contents.
+    BadChecksum { expected: [u8; 20], found: [u8; 20] },
+    /// `mons_no` doesn't correspond to a known species, or `form_no` isn't a
+    /// known form of a species with alternate forms.
+    UnknownSpecies(u16),
+    /// A move id doesn't correspond to a known move.
+    UnknownMove(u16),
+    /// A byte that's expected to always be `0` held something else.
+    NonZeroReserved(u8),
+    /// The save file (or a region within it, like a piibox) isn't the expected size.
+    BadSaveSize,
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::UnexpectedEof => write!(f, "unexpected end of save data"),
+            SaveError::BadChecksum { expected, found } => write!(
+                f,
+                "save data checksum mismatch: expected {expected:02x?}, found {found:02x?}"
+            ),
+            SaveError::UnknownSpecies(id) => write!(f, "unknown species id {id}"),
+            SaveError::UnknownMove(id) => write!(f, "unknown move id {id}"),
+            SaveError::NonZeroReserved(byte) => {
+                write!(f, "expected a reserved byte to be 0, found {byte}")
+            }
+            SaveError::BadSaveSize => write!(f, "save data is an unexpected size"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(_: std::io::Error) -> Self {
+        SaveError::UnexpectedEof
+    }
+}
+
+/// The box of [SDPiiPersonalData] stored in a save file: a 2-byte entry count
+/// followed by that many records. Variable-length, so [FromReader::STATIC_SIZE]
+/// only covers the count header.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PiiBox(pub Vec<SDPiiPersonalData>);
+
+impl PiiBox {
+    pub fn into_vec(self) -> Vec<SDPiiPersonalData> {
+        self.0
+    }
+}
+
+impl FromReader for PiiBox {
+    const STATIC_SIZE: usize = 2;
+
+    fn from_reader<R: std::io::Read + std::io::Seek>(r: &mut R) -> Result<Self, SaveError> {
+        let pii_box_len = r.read_u16::<BigEndian>()?;
+        let entries = (0..pii_box_len)
+            .map(|_| SDPiiPersonalData::from_reader(r))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PiiBox(entries))
+    }
+}
+
+impl ToWriter for PiiBox {
+    fn to_writer<W: std::io::Write>(&self, w: &mut W) -> Result<(), SaveError> {
+        let len: u16 = self.0.len().try_into().map_err(|_| SaveError::BadSaveSize)?;
+        w.write_u16::<BigEndian>(len)?;
+        for pii in &self.0 {
+            pii.to_writer(w)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn extract_piibox(
+    savedata: &[u8],
+    slot: SaveSlot,
+) -> Result<Box<[SDPiiPersonalData]>, SaveError> {
+    let offset = slot.piibox_offset();
     let mut cursor = Cursor::new(savedata);
-    cursor.set_position(PIIBOX_SAVEDATA_OFFSET as u64);
+    cursor.set_position(offset as u64);
 
-    let pii_box_len = cursor.read_u16::<BigEndian>().unwrap();
-    (0..pii_box_len)
-        .map(|_| cursor.read_sd_ppd().unwrap())
-        .collect::<Box<_>>()
+    let pii_box_len = cursor.read_u16::<BigEndian>()?;
+    if pii_box_len as usize > MAX_PIIBOX_LEN {
+        return Err(SaveError::BadSaveSize);
+    }
+
+    cursor.set_position(offset as u64);
+    Ok(PiiBox::from_reader(&mut cursor)?
+        .into_vec()
+        .into_boxed_slice())
 }
 
-pub fn write_piibox(savedata: &mut [u8], pii_box: &[SDPiiPersonalData]) {
+pub fn write_piibox(
+    savedata: &mut [u8],
+    pii_box: &[SDPiiPersonalData],
+    slot: SaveSlot,
+) -> Result<(), SaveError> {
+    if pii_box.len() > MAX_PIIBOX_LEN {
+        return Err(SaveError::BadSaveSize);
+    }
+
     let mut cursor = Cursor::new(savedata);
-    cursor.set_position(PIIBOX_SAVEDATA_OFFSET as u64);
-    cursor.write_u16::<BigEndian>(pii_box.len().try_into().unwrap());
+    cursor.set_position(slot.piibox_offset() as u64);
+    PiiBox(pii_box.to_vec()).to_writer(&mut cursor)
+}
 
-    for pii in pii_box {
-        cursor.write_sd_ppd(pii);
+/// Checks the SHA1 header stored in `decrypted[..20]` against a fresh hash of
+/// `decrypted[20..]`, without mutating anything.
+fn verify_checksum(decrypted: &[u8]) -> Result<(), SaveError> {
+    let mut hasher = Sha1::new();
+    hasher.update(&decrypted[20..]);
+    let expected: [u8; 20] = hasher.finalize().into();
+    let mut found = [0u8; 20];
+    found.copy_from_slice(&decrypted[..20]);
+
+    if expected != found {
+        return Err(SaveError::BadChecksum { expected, found });
     }
+
+    Ok(())
 }
 
 /// See <https://gist.github.com/Lincoln-LM/a12b747d8595f523607a7bae0b7936f0>
-pub fn decrypt_savedata(savedata: &mut [u8]) {
+pub fn decrypt_savedata(savedata: &mut [u8]) -> Result<(), SaveError> {
+    if savedata.len() != SAVEDATA_SIZE {
+        return Err(SaveError::BadSaveSize);
+    }
+
     for chunk_idx in (1..CHUNK_COUNT).rev() {
         // the chunk's offset in `savedata.bin`
         let chunk_pos: usize = chunk_idx * CHUNK_SIZE;
@@ -46,10 +188,7 @@ pub fn decrypt_savedata(savedata: &mut [u8]) {
         }
     }
 
-    let mut hasher = Sha1::new();
-    hasher.update(&savedata[20..]);
-    let result = hasher.finalize();
-    assert_eq!(result[..], savedata[..20])
+    verify_checksum(savedata)
 }
 
 /// See <https://gist.github.com/Lincoln-LM/a12b747d8595f523607a7bae0b7936f0>
@@ -70,3 +209,181 @@ pub fn encrypt_savedata(savedata: &mut [u8]) {
         }
     }
 }
+
+/// A loaded save file that skips re-hashing and re-encrypting entirely when
+/// nothing has been edited since [`SaveData::load`].
+///
+/// `save()` is all-or-nothing, not a partial recompute over just the changed
+/// range: [`encrypt_savedata`]'s chained additive cipher makes each chunk depend on
+/// the one before it, so encrypting only the edited bytes isn't possible
+/// without also re-encrypting everything after them anyway.
+#[derive(Clone)]
+pub struct SaveData {
+    /// The decrypted buffer exactly as it was at load time, kept around for [`SaveData::diff`].
+    loaded: Vec<u8>,
+    /// The decrypted buffer, with edits from [`SaveData::set_pii_box`] applied.
+    decrypted: Vec<u8>,
+    /// The ciphertext passed to [`SaveData::load`], or produced by the last
+    /// `save()`; returned unchanged by `save()` if nothing is dirty.
+    ciphertext: Vec<u8>,
+    pii_box: PiiBox,
+    slot: SaveSlot,
+    dirty: bool,
+}
+
+impl SaveData {
+    /// Decrypts `ciphertext`, verifying its checksum, and parses the piibox for `slot`.
+    pub fn load(ciphertext: &[u8], slot: SaveSlot) -> Result<Self, SaveError> {
+        let mut decrypted = ciphertext.to_vec();
+        decrypt_savedata(&mut decrypted)?;
+
+        let pii_box = PiiBox(extract_piibox(&decrypted, slot)?.into_vec());
+
+        Ok(SaveData {
+            loaded: decrypted.clone(),
+            decrypted,
+            ciphertext: ciphertext.to_vec(),
+            pii_box,
+            slot,
+            dirty: false,
+        })
+    }
+
+    pub fn pii_box(&self) -> &[SDPiiPersonalData] {
+        &self.pii_box.0
+    }
+
+    /// Replaces the piibox, marking this [SaveData] dirty if it actually changed.
+    pub fn set_pii_box(&mut self, pii_box: Vec<SDPiiPersonalData>) -> Result<(), SaveError> {
+        if pii_box != self.pii_box.0 {
+            write_piibox(&mut self.decrypted, &pii_box, self.slot)?;
+            self.dirty = true;
+        }
+        self.pii_box = PiiBox(pii_box);
+        Ok(())
+    }
+
+    /// Returns the ciphertext to write back out. A no-op that returns the
+    /// current ciphertext (from [`SaveData::load`] or the previous `save()`)
+    /// unless the piibox has been edited since then, in which case the whole buffer is re-hashed and
+    /// re-encrypted (see the struct docs for why this can't be partial).
+    pub fn save(&mut self) -> Result<Vec<u8>, SaveError> {
+        if !self.dirty {
+            return Ok(self.ciphertext.clone());
+        }
+
+        let mut encrypted = self.decrypted.clone();
+        encrypt_savedata(&mut encrypted);
+
+        self.ciphertext = encrypted.clone();
+        self.dirty = false;
+        Ok(encrypted)
+    }
+
+    /// Re-checks the decrypted buffer's SHA1 header without mutating anything.
+    /// Always recomputes the hash over the full buffer; there's no cached
+    /// hash to compare against, since the buffer (and thus its hash) can
+    /// change on every [`SaveData::set_pii_box`] call.
+    pub fn verify_integrity(&self) -> Result<(), SaveError> {
+        verify_checksum(&self.decrypted)
+    }
+
+    /// Byte ranges in the decrypted buffer that differ from what was loaded.
+    pub fn diff(&self) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = None;
+
+        for i in 0..self.decrypted.len() {
+            let changed = self.loaded.get(i) != self.decrypted.get(i);
+            match (changed, start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    ranges.push(s..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            ranges.push(s..self.decrypted.len());
+        }
+
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn max_piibox_len_is_true_max() {
+        assert_eq!(MAX_PIIBOX_LEN, 2256);
+    }
+
+    #[test]
+    fn write_piibox_rejects_oversized_entry_count() {
+        let mut savedata = vec![0u8; SAVEDATA_SIZE];
+        let too_many = vec![SDPiiPersonalData::default(); MAX_PIIBOX_LEN + 1];
+        assert!(matches!(
+            write_piibox(&mut savedata, &too_many, SaveSlot::One),
+            Err(SaveError::BadSaveSize)
+        ));
+    }
+
+    #[test]
+    fn write_piibox_at_max_len_does_not_reach_slot_two() {
+        let mut savedata = vec![0u8; SAVEDATA_SIZE];
+        let entries = vec![SDPiiPersonalData::default(); MAX_PIIBOX_LEN];
+        write_piibox(&mut savedata, &entries, SaveSlot::One).unwrap();
+
+        let end = PIIBOX_SAVEDATA_OFFSET_SLOT_1
+            + PiiBox::STATIC_SIZE
+            + MAX_PIIBOX_LEN * SDPiiPersonalData::STATIC_SIZE;
+        assert!(end <= PIIBOX_SAVEDATA_OFFSET_SLOT_2);
+    }
+
+    /// Builds a valid (encrypted, checksummed) savedata buffer whose slot-1
+    /// piibox holds `entries`.
+    fn sample_ciphertext(entries: &[SDPiiPersonalData]) -> Vec<u8> {
+        let mut savedata = vec![0u8; SAVEDATA_SIZE];
+        write_piibox(&mut savedata, entries, SaveSlot::One).unwrap();
+        encrypt_savedata(&mut savedata);
+        savedata
+    }
+
+    #[test]
+    fn save_data_save_is_noop_when_clean() {
+        let ciphertext = sample_ciphertext(&[]);
+        let mut save_data = SaveData::load(&ciphertext, SaveSlot::One).unwrap();
+        assert_eq!(save_data.save().unwrap(), ciphertext);
+    }
+
+    #[test]
+    fn save_data_save_reencrypts_after_edit() {
+        let ciphertext = sample_ciphertext(&[]);
+        let mut save_data = SaveData::load(&ciphertext, SaveSlot::One).unwrap();
+
+        save_data
+            .set_pii_box(vec![SDPiiPersonalData::default()])
+            .unwrap();
+        let saved = save_data.save().unwrap();
+        assert_ne!(saved, ciphertext);
+
+        let mut decrypted = saved;
+        decrypt_savedata(&mut decrypted).unwrap();
+        assert_eq!(extract_piibox(&decrypted, SaveSlot::One).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn save_data_diff_reports_changed_range_after_edit() {
+        let ciphertext = sample_ciphertext(&[]);
+        let mut save_data = SaveData::load(&ciphertext, SaveSlot::One).unwrap();
+        assert!(save_data.diff().is_empty());
+
+        save_data
+            .set_pii_box(vec![SDPiiPersonalData::default()])
+            .unwrap();
+        assert!(!save_data.diff().is_empty());
+    }
+}
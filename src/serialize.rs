@@ -0,0 +1,23 @@
+//! A uniform binary (de)serialization layer for the save-data format.
+//!
+//! Every on-disk struct declares its layout exactly once, via [FromReader]
+//! and [ToWriter], instead of hand-rolling parallel read/write functions
+//! that can drift out of sync with each other. All integers are big-endian.
+
+use std::io::{Read, Seek, Write};
+
+use crate::save_data::SaveError;
+
+/// A type that can be parsed from a big-endian binary layout.
+pub trait FromReader: Sized {
+    /// Size (in bytes) of this type's fixed-size part in a save file. For a
+    /// variable-length type (like a piibox) this covers only its header.
+    const STATIC_SIZE: usize;
+
+    fn from_reader<R: Read + Seek>(r: &mut R) -> Result<Self, SaveError>;
+}
+
+/// A type that can be written back out to a big-endian binary layout.
+pub trait ToWriter {
+    fn to_writer<W: Write>(&self, w: &mut W) -> Result<(), SaveError>;
+}